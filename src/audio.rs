@@ -7,7 +7,7 @@ use num_traits::ToPrimitive;
 pub fn build_input_stream<T>(
     device: &cpal::Device,
     config: &cpal::StreamConfig,
-    sender: Sender<Vec<f32>>,
+    sender: Sender<Vec<Vec<f32>>>,
 ) -> Result<cpal::Stream>
 where
     T: cpal::Sample + cpal::SizedSample + Send + 'static + ToPrimitive,
@@ -18,11 +18,14 @@ where
     let stream = device.build_input_stream(
         config,
         move |data: &[T], _| {
-            // 入力データを f32 に変換
-            let buffer: Vec<f32> = data.iter().map(|s| s.to_f32().unwrap_or(0.0)).collect();
-            // モノラル化（1チャンネル目のみ使用）
-            let mono: Vec<f32> = buffer.chunks(channels).map(|c| c[0]).collect();
-            sender.send(mono).ok();
+            // インターリーブ入力をチャンネルごとに分離する
+            let mut per_channel: Vec<Vec<f32>> = vec![Vec::new(); channels];
+            for frame in data.chunks(channels) {
+                for (ch, s) in frame.iter().enumerate() {
+                    per_channel[ch].push(s.to_f32().unwrap_or(0.0));
+                }
+            }
+            sender.send(per_channel).ok();
         },
         err_fn,
         None,
@@ -66,7 +69,7 @@ mod tests {
         };
 
         // チャネル作成
-        let (sender, receiver) = unbounded::<Vec<f32>>();
+        let (sender, receiver) = unbounded::<Vec<Vec<f32>>>();
 
         // ストリーム生成
         let stream = build_input_stream::<f32>(&device, &config, sender);
@@ -89,7 +92,7 @@ mod tests {
         let host = cpal::default_host();
         if let Some(device) = host.default_input_device() {
             let config = device.default_input_config().unwrap().config();
-            let (sender, _) = unbounded::<Vec<f32>>();
+            let (sender, _) = unbounded::<Vec<Vec<f32>>>();
             let result = build_input_stream::<f32>(&device, &config, sender);
             assert!(result.is_ok() || result.is_err());
         }
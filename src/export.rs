@@ -0,0 +1,78 @@
+use crate::constants::FFT_SIZE;
+use crate::fft_worker::Spectrogram;
+use anyhow::Result;
+use image::{Rgb, RgbImage};
+use std::path::Path;
+
+/// スペクトログラムを PNG として書き出す
+///
+/// `ui/gui.rs` と同じ対数周波数軸・カラーランプで写像するため、
+/// 保存画像は画面表示と一致する（列＝時間、行＝周波数）。
+pub fn export_png(spec: &Spectrogram, sample_rate: f32, path: impl AsRef<Path>) -> Result<()> {
+    let width = spec.len();
+    let height = spec.first().map(|c| c.len()).unwrap_or(0);
+    if width == 0 || height == 0 {
+        anyhow::bail!("empty spectrogram");
+    }
+
+    let f_min: f32 = 20.0;
+    let f_max = sample_rate / 2.0;
+    let log_min = f_min.log10();
+    let log_max = f_max.log10();
+
+    let mut img = RgbImage::new(width as u32, height as u32);
+    for x in 0..width {
+        let rev_x = width - 1 - x; // 左右反転（表示に合わせる）
+        for y in 0..height {
+            let frac = 1.0 - (y as f32 / height as f32);
+            let freq = 10f32.powf(log_min + frac * (log_max - log_min));
+            let fft_index = ((freq / f_max) * (FFT_SIZE as f32 / 2.0)).round() as usize;
+
+            let val = if fft_index < spec[rev_x].len() {
+                spec[rev_x][fft_index].clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+
+            let intensity = (val * 255.0) as u8;
+            img.put_pixel(
+                x as u32,
+                (height - 1 - y) as u32,
+                Rgb([intensity, intensity / 2, 0]),
+            );
+        }
+    }
+
+    img.save(path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 出力画像の寸法が 列数×ビン数 に一致するか確認
+    #[test]
+    fn test_export_png_dimensions() {
+        // 3列、各列 FFT_SIZE/2 ビン
+        let spec: Spectrogram = vec![vec![0.5; FFT_SIZE / 2]; 3];
+        let path =
+            std::env::temp_dir().join(format!("export_test_{}.png", std::process::id()));
+
+        export_png(&spec, 44100.0, &path).unwrap();
+
+        let img = image::open(&path).unwrap().to_rgb8();
+        assert_eq!(img.width(), 3);
+        assert_eq!(img.height() as usize, FFT_SIZE / 2);
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    /// 空のスペクトログラムはエラーになる
+    #[test]
+    fn test_export_png_empty_bails() {
+        let spec: Spectrogram = Vec::new();
+        let path = std::env::temp_dir().join("export_empty.png");
+        assert!(export_png(&spec, 44100.0, &path).is_err());
+    }
+}
@@ -1,33 +1,134 @@
 use crate::constants::FFT_SIZE;
+use crate::window::WindowKind;
 use crossbeam_channel::Receiver;
-use rustfft::{FftPlanner, num_complex::Complex};
+use realfft::RealFftPlanner;
+use ringbuf::{
+    HeapRb,
+    traits::{Consumer, Observer, RingBuffer},
+};
 use std::sync::{Arc, Mutex};
 
-/// FFTスレッドを起動し、リアルタイムでスペクトログラムを更新
-pub fn start_fft_thread(rx: Receiver<Vec<f32>>, spec_ref: Arc<Mutex<Vec<Vec<f32>>>>) {
+/// 1チャンネル分のスペクトログラム（時間列×周波数ビン）
+pub type Spectrogram = Vec<Vec<f32>>;
+
+/// dBFS 表示レンジ
+///
+/// ビンの大きさを `[floor_db, ceil_db]` で切り、0..1 に正規化する。
+/// `floor_db` を上げればノイズフロアを持ち上げ、`ceil_db` を下げれば
+/// 大音量を圧縮できる。
+#[derive(Debug, Clone, Copy)]
+pub struct DbRange {
+    pub floor_db: f32,
+    pub ceil_db: f32,
+}
+
+impl Default for DbRange {
+    fn default() -> Self {
+        Self {
+            floor_db: -80.0,
+            ceil_db: 0.0,
+        }
+    }
+}
+
+impl DbRange {
+    /// dBFS 値をレンジで切り、0..1 へ正規化する
+    pub fn normalize(&self, db: f32) -> f32 {
+        let span = (self.ceil_db - self.floor_db).max(f32::EPSILON);
+        ((db - self.floor_db) / span).clamp(0.0, 1.0)
+    }
+}
+
+/// 既定のフレーム間ホップ幅（サンプル数）。`FFT_SIZE` に対して 1/4 = 75% オーバーラップ。
+///
+/// 表示の更新レートを `FFT_SIZE` から切り離し、速い過渡を見えるようにする。
+pub const DEFAULT_HOP: usize = FFT_SIZE / 4;
+
+/// FFTスレッドを起動し、チャンネルごとに独立してスペクトログラムを更新する
+///
+/// `rx` はチャンネル主順のサンプル列（`chunk[ch]`）を受け取り、
+/// 共有領域 `spec_ref` は `spec_ref[ch]` が各チャンネルのスペクトログラムを持つ。
+/// `hop` はフレーム間のホップ幅（`1..=FFT_SIZE`）。
+pub fn start_fft_thread(
+    rx: Receiver<Vec<Vec<f32>>>,
+    spec_ref: Arc<Mutex<Vec<Spectrogram>>>,
+    window: WindowKind,
+    hop: usize,
+    db_range: Arc<Mutex<DbRange>>,
+) {
+    let hop = hop.clamp(1, FFT_SIZE);
     std::thread::spawn(move || {
-        let mut planner = FftPlanner::new();
+        let channels = spec_ref.lock().unwrap().len();
+
+        // 実数入力専用のプランを一度だけ作り、入出力バッファとスクラッチを使い回す
+        let mut planner = RealFftPlanner::<f32>::new();
         let fft = planner.plan_fft_forward(FFT_SIZE);
-        let mut buffer = Vec::<f32>::new();
+        let mut input = fft.make_input_vec();
+        let mut output = fft.make_output_vec();
+        let mut scratch = fft.make_scratch_vec();
+
+        // チャンネルごとに、直近 `FFT_SIZE` サンプルを保持するリングバッファを持つ。
+        // 新しいサンプルは古いものを上書きしていく。
+        let mut rings: Vec<HeapRb<f32>> =
+            (0..channels).map(|_| HeapRb::<f32>::new(FFT_SIZE)).collect();
+        // 前回フレームを出してから到着した新規サンプル数（チャンネルごと）
+        let mut new_since_frame = vec![0usize; channels];
+        // 初回充填済みかどうか（チャンネルごと）
+        let mut primed = vec![false; channels];
+
+        // 窓係数とコヒーレントゲインはループ外で一度だけ計算する
+        let coeffs = window.coefficients();
+        let coherent_gain = WindowKind::coherent_gain(&coeffs);
 
         for chunk in rx {
-            buffer.extend(chunk);
-            while buffer.len() >= FFT_SIZE {
-                let frame: Vec<f32> = buffer.drain(..FFT_SIZE).collect();
-                let mut input: Vec<Complex<f32>> = frame
-                    .into_iter()
-                    .map(|x| Complex { re: x, im: 0.0 })
-                    .collect();
-                fft.process(&mut input);
-
-                let mags: Vec<f32> = input[..FFT_SIZE / 2]
-                    .iter()
-                    .map(|c| (c.norm() / (FFT_SIZE as f32)).log10().max(-2.0) + 2.0)
-                    .collect();
-
-                let mut spec = spec_ref.lock().unwrap();
-                spec.pop();
-                spec.insert(0, mags);
+            for (ch, samples) in chunk.into_iter().enumerate() {
+                if ch >= channels {
+                    continue;
+                }
+                for sample in samples {
+                    rings[ch].push_overwrite(sample);
+                    if !rings[ch].is_full() {
+                        // まだ1フレーム分溜まっていない
+                        continue;
+                    }
+                    if !primed[ch] {
+                        // 初回充填時は1列だけ出す（重複列を避ける）
+                        primed[ch] = true;
+                        new_since_frame[ch] = hop;
+                    } else {
+                        new_since_frame[ch] += 1;
+                    }
+
+                    // HOP 以上の新規サンプルが溜まるたびに1列出力する
+                    while new_since_frame[ch] >= hop {
+                        // 窓掛けした実数サンプルを入力バッファへ直接書き込む
+                        for (slot, (n, &x)) in
+                            input.iter_mut().zip(rings[ch].iter().enumerate())
+                        {
+                            *slot = x * coeffs[n];
+                        }
+                        fft.process_with_scratch(&mut input, &mut output, &mut scratch)
+                            .expect("realfft process failed");
+
+                        // 窓掛けによる減衰はコヒーレントゲインで補正し、
+                        // dBFS に変換して表示レンジで 0..1 へ正規化する
+                        let range = *db_range.lock().unwrap();
+                        let mags: Vec<f32> = output[..FFT_SIZE / 2]
+                            .iter()
+                            .map(|c| {
+                                let norm = c.norm() / (FFT_SIZE as f32 * coherent_gain);
+                                let db = 20.0 * norm.log10();
+                                range.normalize(db)
+                            })
+                            .collect();
+
+                        let mut spec = spec_ref.lock().unwrap();
+                        spec[ch].pop();
+                        spec[ch].insert(0, mags);
+
+                        new_since_frame[ch] -= hop;
+                    }
+                }
             }
         }
     });
@@ -42,15 +143,21 @@ mod tests {
     /// FFTスレッドが正しくスペクトログラムを更新するかを確認
     #[test]
     fn test_start_fft_thread_updates_spec_ref() {
-        let spec_ref = Arc::new(Mutex::new(vec![vec![0.0; FFT_SIZE / 2]; 5]));
+        let spec_ref = Arc::new(Mutex::new(vec![vec![vec![0.0; FFT_SIZE / 2]; 5]]));
         let spec_clone = spec_ref.clone();
-        let (tx, rx) = unbounded::<Vec<f32>>();
+        let (tx, rx) = unbounded::<Vec<Vec<f32>>>();
 
         // FFTスレッドを開始
-        start_fft_thread(rx, spec_ref);
+        start_fft_thread(
+            rx,
+            spec_ref,
+            WindowKind::Hann,
+            DEFAULT_HOP,
+            Arc::new(Mutex::new(DbRange::default())),
+        );
 
-        // FFT_SIZE 分のデータを送信
-        tx.send(vec![1.0; FFT_SIZE]).unwrap();
+        // FFT_SIZE 分のデータを送信（1チャンネル）
+        tx.send(vec![vec![1.0; FFT_SIZE]]).unwrap();
 
         // スレッドが処理を終えるまで待つ（送信側をすぐにdropしない）
         thread::sleep(Duration::from_millis(500));
@@ -60,29 +167,55 @@ mod tests {
 
         // 更新されたか確認
         let spec = spec_clone.lock().unwrap();
-        let updated = spec.iter().any(|col| col.iter().any(|&x| x > 0.0));
+        let updated = spec[0].iter().any(|col| col.iter().any(|&x| x > 0.0));
         assert!(updated, "スペクトログラムが更新されていません");
     }
 
     /// 複数チャンクを処理できるか確認
     #[test]
     fn test_fft_thread_handles_multiple_chunks() {
-        let spec_ref = Arc::new(Mutex::new(vec![vec![0.0; FFT_SIZE / 2]; 5]));
+        let spec_ref = Arc::new(Mutex::new(vec![vec![vec![0.0; FFT_SIZE / 2]; 5]]));
         let spec_clone = spec_ref.clone();
-        let (tx, rx) = unbounded::<Vec<f32>>();
+        let (tx, rx) = unbounded::<Vec<Vec<f32>>>();
 
-        start_fft_thread(rx, spec_ref);
+        start_fft_thread(
+            rx,
+            spec_ref,
+            WindowKind::Hann,
+            DEFAULT_HOP,
+            Arc::new(Mutex::new(DbRange::default())),
+        );
 
         // 2回分送信
-        tx.send(vec![0.5; FFT_SIZE]).unwrap();
-        tx.send(vec![0.2; FFT_SIZE]).unwrap();
+        tx.send(vec![vec![0.5; FFT_SIZE]]).unwrap();
+        tx.send(vec![vec![0.2; FFT_SIZE]]).unwrap();
 
         // しばらく待つ（スレッドが処理完了するまで）
         thread::sleep(Duration::from_millis(800));
         drop(tx); // スレッド終了を促す
 
         let spec = spec_clone.lock().unwrap();
-        let updated = spec.iter().any(|col| col.iter().any(|&x| x > 0.0));
+        let updated = spec[0].iter().any(|col| col.iter().any(|&x| x > 0.0));
         assert!(updated, "複数チャンクの処理が行われていません");
     }
+
+    /// dBFS レンジが範囲外を 0/1 に切り、ゼロ幅でも破綻しないことを確認
+    #[test]
+    fn test_db_range_normalize() {
+        let r = DbRange {
+            floor_db: -80.0,
+            ceil_db: 0.0,
+        };
+        assert_eq!(r.normalize(-100.0), 0.0);
+        assert_eq!(r.normalize(10.0), 1.0);
+        assert_eq!(r.normalize(-40.0), 0.5);
+
+        // ゼロ幅レンジでもゼロ除算にならない
+        let z = DbRange {
+            floor_db: -20.0,
+            ceil_db: -20.0,
+        };
+        let v = z.normalize(-20.0);
+        assert!(v.is_finite() && (0.0..=1.0).contains(&v));
+    }
 }
@@ -1,53 +1,80 @@
 mod audio;
 mod constants;
+mod export;
 mod fft_worker;
+mod source;
 mod ui;
 mod util;
+mod window;
 
-use audio::build_input_stream;
 use constants::{FFT_SIZE, SPEC_WIDTH};
-use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use crossbeam_channel::unbounded;
-use fft_worker::start_fft_thread;
+use fft_worker::{DEFAULT_HOP, DbRange, start_fft_thread};
+use source::{FileSource, LiveSource, Source};
 use std::sync::{Arc, Mutex};
+use window::WindowKind;
 
 fn main() -> anyhow::Result<()> {
-    // コマンドライン引数でUIモードを選択
+    // コマンドライン引数でUIモードと入力ソースを選択
     let args: Vec<String> = std::env::args().collect();
     let use_gui = args.iter().any(|a| a == "--gui");
+    let file = args
+        .iter()
+        .position(|a| a == "--file")
+        .and_then(|i| args.get(i + 1).cloned());
+    // --window hann|hamming|blackman|rect（既定 Hann）
+    let window = args
+        .iter()
+        .position(|a| a == "--window")
+        .and_then(|i| args.get(i + 1))
+        .and_then(|s| WindowKind::from_flag(s))
+        .unwrap_or_default();
+    // --hop N（既定 FFT_SIZE/4）
+    let hop = args
+        .iter()
+        .position(|a| a == "--hop")
+        .and_then(|i| args.get(i + 1))
+        .and_then(|s| s.parse::<usize>().ok())
+        .unwrap_or(DEFAULT_HOP);
 
-    // === 音声デバイス初期化 ===
-    let host = cpal::default_host();
-    let device = host.default_input_device().expect("no input device");
-    println!("使用デバイス: {}", device.name()?);
-    let config = device.default_input_config()?;
-    let sample_rate = config.sample_rate().0 as f32;
+    // === 入力ソース初期化 ===
+    let source: Box<dyn Source> = match file {
+        Some(path) => Box::new(FileSource::open(path)?),
+        None => Box::new(LiveSource::from_default()?),
+    };
+    let sample_rate = source.sample_rate();
+    let channels = source.channels().max(1);
 
     // === チャンネル作成 ===
-    let (tx, rx) = unbounded::<Vec<f32>>();
-
-    // === ストリーム構築 ===
-    println!("サンプルフォーマット: {:?}", config.sample_format());
-    let stream = match config.sample_format() {
-        cpal::SampleFormat::F32 => build_input_stream::<f32>(&device, &config.into(), tx.clone())?,
-        cpal::SampleFormat::I16 => build_input_stream::<i16>(&device, &config.into(), tx.clone())?,
-        cpal::SampleFormat::U16 => build_input_stream::<u16>(&device, &config.into(), tx.clone())?,
-        _ => panic!("unsupported format"),
-    };
-    stream.play()?;
+    let (tx, rx) = unbounded::<Vec<Vec<f32>>>();
+
+    // === ソース起動（ハンドルはプログラム終了まで保持する）===
+    let _handle = source.start(tx)?;
+
+    // === スペクトログラム共有領域（チャンネルごと）===
+    let spectrogram = Arc::new(Mutex::new(vec![
+        vec![vec![0.0; FFT_SIZE / 2]; SPEC_WIDTH];
+        channels
+    ]));
 
-    // === スペクトログラム共有領域 ===
-    let spectrogram = Arc::new(Mutex::new(vec![vec![0.0; FFT_SIZE / 2]; SPEC_WIDTH]));
+    // === dBFS 表示レンジ（CLI から実行時に調整可能）===
+    let db_range = Arc::new(Mutex::new(DbRange::default()));
 
     // === FFTスレッド起動 ===
-    start_fft_thread(rx, Arc::clone(&spectrogram));
+    start_fft_thread(
+        rx,
+        Arc::clone(&spectrogram),
+        window,
+        hop,
+        Arc::clone(&db_range),
+    );
 
     // === UI起動 ===
     if use_gui {
         println!("GUIモードで起動します。");
-        ui::gui::run_ui(sample_rate, spectrogram)
+        ui::gui::run_ui(sample_rate, channels, spectrogram)
     } else {
         println!("CLIモードで起動します。");
-        ui::cli::run_ui(sample_rate, spectrogram)
+        ui::cli::run_ui(sample_rate, channels, spectrogram, db_range)
     }
 }
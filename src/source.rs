@@ -0,0 +1,199 @@
+use crate::audio::build_input_stream;
+use anyhow::{Context, Result};
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use crossbeam_channel::Sender;
+use std::path::PathBuf;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// 取得開始後にソースを生かし続けるためのハンドル
+///
+/// drop されるとライブストリームは停止し、ファイル再生スレッドは切り離される。
+pub enum SourceHandle {
+    /// cpal のライブ入力ストリーム
+    Live(cpal::Stream),
+    /// ファイルを実時間で流し込む再生スレッド
+    File(JoinHandle<()>),
+}
+
+/// サンプル取得元の抽象
+///
+/// 実装は FFT スレッドが消費するのと同じ `Sender<Vec<Vec<f32>>>` へチャンネル主順のサンプルを流し込む。
+pub trait Source {
+    /// UI へ報告するサンプルレート
+    fn sample_rate(&self) -> f32;
+
+    /// チャンネル数
+    fn channels(&self) -> usize;
+
+    /// 取得を開始し、チャンネル主順のサンプル（`chunk[ch]`）を `tx` へ流し込む
+    fn start(self: Box<Self>, tx: Sender<Vec<Vec<f32>>>) -> Result<SourceHandle>;
+}
+
+/// マイク等のライブ入力（既存の cpal 経路）
+pub struct LiveSource {
+    device: cpal::Device,
+    config: cpal::SupportedStreamConfig,
+}
+
+impl LiveSource {
+    /// デフォルト入力デバイスからソースを構築する
+    pub fn from_default() -> Result<Self> {
+        let host = cpal::default_host();
+        let device = host
+            .default_input_device()
+            .context("no input device")?;
+        println!("使用デバイス: {}", device.name()?);
+        let config = device.default_input_config()?;
+        Ok(Self { device, config })
+    }
+}
+
+impl Source for LiveSource {
+    fn sample_rate(&self) -> f32 {
+        self.config.sample_rate().0 as f32
+    }
+
+    fn channels(&self) -> usize {
+        self.config.channels() as usize
+    }
+
+    fn start(self: Box<Self>, tx: Sender<Vec<Vec<f32>>>) -> Result<SourceHandle> {
+        println!("サンプルフォーマット: {:?}", self.config.sample_format());
+        let format = self.config.sample_format();
+        let config: cpal::StreamConfig = self.config.into();
+        let stream = match format {
+            cpal::SampleFormat::F32 => build_input_stream::<f32>(&self.device, &config, tx)?,
+            cpal::SampleFormat::I16 => build_input_stream::<i16>(&self.device, &config, tx)?,
+            cpal::SampleFormat::U16 => build_input_stream::<u16>(&self.device, &config, tx)?,
+            _ => anyhow::bail!("unsupported format"),
+        };
+        stream.play()?;
+        Ok(SourceHandle::Live(stream))
+    }
+}
+
+/// WAV ファイルを実時間で再生しながら解析するソース
+pub struct FileSource {
+    path: PathBuf,
+    spec: hound::WavSpec,
+    /// チャンネル主順のサンプル（`samples[ch]`）
+    samples: Vec<Vec<f32>>,
+}
+
+impl FileSource {
+    /// WAV を `hound` でデコードし、チャンネルごとに分離する
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let mut reader = hound::WavReader::open(&path)
+            .with_context(|| format!("failed to open WAV: {}", path.display()))?;
+        let spec = reader.spec();
+        let channels = spec.channels as usize;
+
+        // サンプル値を -1.0..1.0 の f32 へ正規化する
+        let interleaved: Vec<f32> = match spec.sample_format {
+            hound::SampleFormat::Float => {
+                reader.samples::<f32>().collect::<Result<_, _>>()?
+            }
+            hound::SampleFormat::Int => {
+                let max = (1i64 << (spec.bits_per_sample - 1)) as f32;
+                reader
+                    .samples::<i32>()
+                    .map(|s| s.map(|v| v as f32 / max))
+                    .collect::<Result<_, _>>()?
+            }
+        };
+
+        // インターリーブをチャンネルごとに分離する
+        let mut samples: Vec<Vec<f32>> = vec![Vec::new(); channels];
+        for frame in interleaved.chunks(channels) {
+            for (ch, &s) in frame.iter().enumerate() {
+                samples[ch].push(s);
+            }
+        }
+
+        Ok(Self {
+            path,
+            spec,
+            samples,
+        })
+    }
+}
+
+impl Source for FileSource {
+    fn sample_rate(&self) -> f32 {
+        self.spec.sample_rate as f32
+    }
+
+    fn channels(&self) -> usize {
+        self.spec.channels as usize
+    }
+
+    fn start(self: Box<Self>, tx: Sender<Vec<Vec<f32>>>) -> Result<SourceHandle> {
+        println!("ファイルを再生します: {}", self.path.display());
+        let sample_rate = self.spec.sample_rate as f32;
+        // 約 20ms ぶんを1チャンクとして実時間で送出する
+        let chunk_len = (sample_rate * 0.02) as usize + 1;
+        let total = self.samples.iter().map(|c| c.len()).max().unwrap_or(0);
+
+        let handle = std::thread::spawn(move || {
+            let start = Instant::now();
+            let mut offset = 0usize;
+            while offset < total {
+                let end = (offset + chunk_len).min(total);
+                let block: Vec<Vec<f32>> = self
+                    .samples
+                    .iter()
+                    .map(|c| c[offset.min(c.len())..end.min(c.len())].to_vec())
+                    .collect();
+                if tx.send(block).is_err() {
+                    break;
+                }
+                offset = end;
+                // サンプルレートに合わせて壁時計へ追従させる
+                let target = Duration::from_secs_f32(offset as f32 / sample_rate);
+                if let Some(wait) = target.checked_sub(start.elapsed()) {
+                    std::thread::sleep(wait);
+                }
+            }
+        });
+
+        Ok(SourceHandle::File(handle))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// ステレオ WAV をデコードしてチャンネル分離・正規化できるか確認
+    #[test]
+    fn test_file_source_open_stereo() {
+        let spec = hound::WavSpec {
+            channels: 2,
+            sample_rate: 8000,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let path =
+            std::env::temp_dir().join(format!("src_test_{}.wav", std::process::id()));
+
+        let mut writer = hound::WavWriter::create(&path, spec).unwrap();
+        // L=16384(0.5), R=-16384(-0.5) を2フレーム
+        for _ in 0..2 {
+            writer.write_sample(16384i16).unwrap();
+            writer.write_sample(-16384i16).unwrap();
+        }
+        writer.finalize().unwrap();
+
+        let src = FileSource::open(&path).unwrap();
+        assert_eq!(src.channels(), 2);
+        assert_eq!(src.sample_rate(), 8000.0);
+        assert_eq!(src.samples.len(), 2);
+        assert_eq!(src.samples[0].len(), 2);
+        assert!((src.samples[0][0] - 0.5).abs() < 1e-3);
+        assert!((src.samples[1][0] + 0.5).abs() < 1e-3);
+
+        std::fs::remove_file(&path).ok();
+    }
+}
@@ -1,5 +1,6 @@
 use crate::{
     constants::{FFT_SIZE, SPEC_WIDTH},
+    fft_worker::{DbRange, Spectrogram},
     util::intensity_color,
 };
 use crossterm::{
@@ -18,7 +19,12 @@ use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 /// ターミナルを起動してリアルタイム描画を行う
-pub fn run_ui(sample_rate: f32, spectrogram: Arc<Mutex<Vec<Vec<f32>>>>) -> anyhow::Result<()> {
+pub fn run_ui(
+    sample_rate: f32,
+    channels: usize,
+    spectrogram: Arc<Mutex<Vec<Spectrogram>>>,
+    db_range: Arc<Mutex<DbRange>>,
+) -> anyhow::Result<()> {
     enable_raw_mode()?;
     let mut stdout = std::io::stdout();
     execute!(stdout, EnterAlternateScreen)?;
@@ -26,60 +32,80 @@ pub fn run_ui(sample_rate: f32, spectrogram: Arc<Mutex<Vec<Vec<f32>>>>) -> anyho
     let mut terminal = Terminal::new(backend)?;
     terminal.clear()?;
 
+    // 表示中のチャンネルと、全チャンネルを縦に並べるかどうか
+    let mut selected = 0usize;
+    let mut stacked = false;
+    // PNG 保存時の連番
+    let mut shot = 0usize;
+
     loop {
         if event::poll(Duration::from_millis(10))? {
             if let Event::Key(key) = event::read()? {
-                if key.code == KeyCode::Char('q') {
-                    break;
+                match key.code {
+                    KeyCode::Char('q') => break,
+                    // Tab / 'c' で表示チャンネルを切り替える
+                    KeyCode::Tab | KeyCode::Char('c') => {
+                        selected = (selected + 1) % channels;
+                    }
+                    // 'v' で単一チャンネル表示とスタック表示を切り替える
+                    KeyCode::Char('v') => stacked = !stacked,
+                    // 上下でノイズフロア、左右で上限を 5dB ずつ調整する
+                    KeyCode::Up | KeyCode::Down => {
+                        let mut r = db_range.lock().unwrap();
+                        let delta = if key.code == KeyCode::Up { 5.0 } else { -5.0 };
+                        r.floor_db = (r.floor_db + delta).clamp(-120.0, r.ceil_db - 5.0);
+                    }
+                    KeyCode::Right | KeyCode::Left => {
+                        let mut r = db_range.lock().unwrap();
+                        let delta = if key.code == KeyCode::Right { 5.0 } else { -5.0 };
+                        r.ceil_db = (r.ceil_db + delta).clamp(r.floor_db + 5.0, 0.0);
+                    }
+                    // 's' で表示中チャンネルを PNG として保存する
+                    KeyCode::Char('s') => {
+                        let spec = spectrogram.lock().unwrap();
+                        let ch = selected.min(spec.len().saturating_sub(1));
+                        let path = format!("spectrogram_ch{}_{}.png", ch, shot);
+                        if crate::export::export_png(&spec[ch], sample_rate, &path).is_ok() {
+                            shot += 1;
+                        }
+                    }
+                    _ => {}
                 }
             }
         }
 
         let spec = spectrogram.lock().unwrap().clone();
+        let range = *db_range.lock().unwrap();
 
         terminal.draw(|f| {
             let size = f.area();
-            let block = Block::default()
-                .borders(Borders::ALL)
-                .title("Spectrogram (press 'q' to quit)");
+            let scope = if stacked {
+                "all channels".to_string()
+            } else {
+                format!("ch{}/{}", selected, channels)
+            };
+            let title = format!(
+                "Spectrogram {} [{:.0}..{:.0}dB] ('q' quit / Tab chan / 'v' stack / 's' save / arrows dB)",
+                scope, range.floor_db, range.ceil_db
+            );
+            let block = Block::default().borders(Borders::ALL).title(title);
             f.render_widget(&block, size);
             let inner = block.inner(size);
 
             let width = inner.width.min(SPEC_WIDTH as u16) as usize;
-            let height = inner.height as usize;
-
-            let f_min: f32 = 20.0;
-            let f_max: f32 = sample_rate / 2.0;
-            let log_min = f_min.log10();
-            let log_max = f_max.log10();
+            let total_height = inner.height as usize;
 
             let mut lines: Vec<Line> = Vec::new();
-            for row in 0..height {
-                let frac = 1.0 - row as f32 / height as f32;
-                let freq = 10f32.powf(log_min + frac * (log_max - log_min));
-
-                let label = if row % (height / 8).max(1) == 0 {
-                    format!("{:>6.0}Hz | ", freq)
-                } else {
-                    "         | ".to_string()
-                };
-
-                let mut spans: Vec<Span> = vec![Span::raw(label)];
-                for column in spec.iter().rev().take(width) {
-                    let fft_index = ((freq / f_max) * (FFT_SIZE as f32 / 2.0)).round() as usize;
-                    if fft_index < column.len() {
-                        let val = column[fft_index];
-                        let intensity = ((val * 10.0) as u8).min(9);
-                        let ch = " .:-=+*#%@".chars().nth(intensity as usize).unwrap_or(' ');
-                        spans.push(Span::styled(
-                            ch.to_string(),
-                            Style::default().fg(intensity_color(val)),
-                        ));
-                    } else {
-                        spans.push(Span::raw(" "));
-                    }
+            if stacked {
+                // 高さをチャンネル数で等分して縦に積む
+                let per = (total_height / channels.max(1)).max(1);
+                for (ch, chan_spec) in spec.iter().enumerate() {
+                    lines.push(Line::from(format!("-- ch{} --", ch)));
+                    lines.extend(channel_lines(chan_spec, sample_rate, width, per.saturating_sub(1)));
                 }
-                lines.push(Line::from(spans));
+            } else {
+                let chan_spec = &spec[selected.min(spec.len().saturating_sub(1))];
+                lines.extend(channel_lines(chan_spec, sample_rate, width, total_height));
             }
 
             f.render_widget(Paragraph::new(lines), inner);
@@ -91,3 +117,41 @@ pub fn run_ui(sample_rate: f32, spectrogram: Arc<Mutex<Vec<Vec<f32>>>>) -> anyho
     terminal.show_cursor()?;
     Ok(())
 }
+
+/// 1チャンネル分のスペクトログラムを `height` 行のテキストに描画する
+fn channel_lines(spec: &Spectrogram, sample_rate: f32, width: usize, height: usize) -> Vec<Line> {
+    let f_min: f32 = 20.0;
+    let f_max: f32 = sample_rate / 2.0;
+    let log_min = f_min.log10();
+    let log_max = f_max.log10();
+
+    let mut lines: Vec<Line> = Vec::new();
+    for row in 0..height {
+        let frac = 1.0 - row as f32 / height as f32;
+        let freq = 10f32.powf(log_min + frac * (log_max - log_min));
+
+        let label = if row % (height / 8).max(1) == 0 {
+            format!("{:>6.0}Hz | ", freq)
+        } else {
+            "         | ".to_string()
+        };
+
+        let mut spans: Vec<Span> = vec![Span::raw(label)];
+        for column in spec.iter().rev().take(width) {
+            let fft_index = ((freq / f_max) * (FFT_SIZE as f32 / 2.0)).round() as usize;
+            if fft_index < column.len() {
+                let val = column[fft_index];
+                let intensity = ((val * 10.0) as u8).min(9);
+                let ch = " .:-=+*#%@".chars().nth(intensity as usize).unwrap_or(' ');
+                spans.push(Span::styled(
+                    ch.to_string(),
+                    Style::default().fg(intensity_color(val)),
+                ));
+            } else {
+                spans.push(Span::raw(" "));
+            }
+        }
+        lines.push(Line::from(spans));
+    }
+    lines
+}
@@ -1,57 +1,73 @@
 use crate::constants::FFT_SIZE;
+use crate::fft_worker::Spectrogram;
 use eframe::egui::{self, Color32, TextureHandle};
 use std::sync::{Arc, Mutex};
 
 /// GUIモードでのターミナル描画を行う
-pub fn run_ui(sample_rate: f32, spectrogram: Arc<Mutex<Vec<Vec<f32>>>>) -> anyhow::Result<()> {
+pub fn run_ui(
+    sample_rate: f32,
+    channels: usize,
+    spectrogram: Arc<Mutex<Vec<Spectrogram>>>,
+) -> anyhow::Result<()> {
     println!("GUIモードで起動します。");
     let options = eframe::NativeOptions::default();
 
     let _ = eframe::run_native(
         "Spectrogram Viewer",
         options,
-        Box::new(|_cc| Ok(Box::new(SpectrogramApp::new(sample_rate, spectrogram)))),
+        Box::new(move |_cc| {
+            Ok(Box::new(SpectrogramApp::new(
+                sample_rate,
+                channels,
+                spectrogram,
+            )))
+        }),
     );
     Ok(())
 }
 
 struct SpectrogramApp {
     sample_rate: f32,
-    spectrogram: Arc<Mutex<Vec<Vec<f32>>>>,
+    channels: usize,
+    /// 単一チャンネル表示時に選択しているチャンネル
+    selected: usize,
+    /// 左右のチャンネルを横に並べて表示するか
+    side_by_side: bool,
+    spectrogram: Arc<Mutex<Vec<Spectrogram>>>,
     texture: Option<TextureHandle>,
+    /// PNG 保存時の連番
+    shot: usize,
 }
 
 impl eframe::App for SpectrogramApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        let spec = self.spectrogram.lock().unwrap().clone();
-        let width = spec.len();
-        let height = spec[0].len();
-
-        // 黒で初期化
-        let pixels = vec![Color32::BLACK; width * height];
-        let mut image = egui::ColorImage::new([width, height], pixels);
+        // Tab で表示チャンネル切替、V で横並び表示を切替
+        ctx.input(|i| {
+            if i.key_pressed(egui::Key::Tab) {
+                self.selected = (self.selected + 1) % self.channels.max(1);
+            }
+            if i.key_pressed(egui::Key::V) {
+                self.side_by_side = !self.side_by_side;
+            }
+        });
 
-        let f_min: f32 = 20.0;
-        let f_max = self.sample_rate / 2.0;
-        let log_min = f_min.log10();
-        let log_max = f_max.log10();
+        let spec = self.spectrogram.lock().unwrap().clone();
+        let chan_width = spec.first().map(|c| c.len()).unwrap_or(0);
+        let height = spec.first().and_then(|c| c.first()).map(|b| b.len()).unwrap_or(0);
 
-        for x in 0..width {
-            let rev_x = width - 1 - x; // 左右反転
-            for y in 0..height {
-                let frac = 1.0 - (y as f32 / height as f32);
-                let freq = 10f32.powf(log_min + frac * (log_max - log_min));
-                let fft_index = ((freq / f_max) * (FFT_SIZE as f32 / 2.0)).round() as usize;
+        // 表示するチャンネルの一覧
+        let shown: Vec<usize> = if self.side_by_side {
+            (0..spec.len()).collect()
+        } else {
+            vec![self.selected.min(spec.len().saturating_sub(1))]
+        };
 
-                let val = if fft_index < spec[rev_x].len() {
-                    spec[rev_x][fft_index].clamp(0.0, 2.0)
-                } else {
-                    0.0
-                };
+        let total_width = chan_width * shown.len();
+        let pixels = vec![Color32::BLACK; total_width.max(1) * height.max(1)];
+        let mut image = egui::ColorImage::new([total_width.max(1), height.max(1)], pixels);
 
-                let intensity = ((val / 2.0) * 255.0) as u8;
-                image[(x, height - 1 - y)] = egui::Color32::from_rgb(intensity, intensity / 2, 0);
-            }
+        for (slot, &ch) in shown.iter().enumerate() {
+            self.fill_channel(&mut image, slot * chan_width, &spec[ch], chan_width, height);
         }
 
         // 🟢 最初だけロードして、以降は更新
@@ -62,22 +78,73 @@ impl eframe::App for SpectrogramApp {
                 Some(ctx.load_texture("spectrogram", image, egui::TextureOptions::NEAREST));
         }
 
+        let mut save = false;
         egui::CentralPanel::default().show(ctx, |ui| {
+            if ui.button("Save PNG").clicked() {
+                save = true;
+            }
             if let Some(texture) = &self.texture {
                 ui.image((texture.id(), ui.available_size()));
             }
         });
 
+        // 単一チャンネル表示時は選択中のチャンネル、横並び表示時は先頭チャンネル（ch0）だけを保存する
+        if save {
+            let ch = shown.first().copied().unwrap_or(0);
+            let path = format!("spectrogram_ch{}_{}.png", ch, self.shot);
+            if crate::export::export_png(&spec[ch], self.sample_rate, &path).is_ok() {
+                self.shot += 1;
+            }
+        }
+
         ctx.request_repaint();
     }
 }
 
 impl SpectrogramApp {
-    fn new(sample_rate: f32, spectrogram: Arc<Mutex<Vec<Vec<f32>>>>) -> Self {
+    fn new(sample_rate: f32, channels: usize, spectrogram: Arc<Mutex<Vec<Spectrogram>>>) -> Self {
         Self {
-            sample_rate: sample_rate,
-            spectrogram: spectrogram,
+            sample_rate,
+            channels,
+            selected: 0,
+            side_by_side: false,
+            spectrogram,
             texture: None,
+            shot: 0,
+        }
+    }
+
+    /// 1チャンネル分を `x_offset` 列目から幅 `width` の縦帯（全高）へ描画する
+    fn fill_channel(
+        &self,
+        image: &mut egui::ColorImage,
+        x_offset: usize,
+        spec: &Spectrogram,
+        width: usize,
+        height: usize,
+    ) {
+        let f_min: f32 = 20.0;
+        let f_max = self.sample_rate / 2.0;
+        let log_min = f_min.log10();
+        let log_max = f_max.log10();
+
+        for x in 0..width {
+            let rev_x = width - 1 - x; // 左右反転
+            for y in 0..height {
+                let frac = 1.0 - (y as f32 / height as f32);
+                let freq = 10f32.powf(log_min + frac * (log_max - log_min));
+                let fft_index = ((freq / f_max) * (FFT_SIZE as f32 / 2.0)).round() as usize;
+
+                let val = if fft_index < spec[rev_x].len() {
+                    spec[rev_x][fft_index].clamp(0.0, 1.0)
+                } else {
+                    0.0
+                };
+
+                let intensity = (val * 255.0) as u8;
+                image[(x_offset + x, height - 1 - y)] =
+                    egui::Color32::from_rgb(intensity, intensity / 2, 0);
+            }
         }
     }
 }
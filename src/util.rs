@@ -1,14 +1,14 @@
 use ratatui::style::Color;
 
-/// 振幅に応じた色を返す
+/// 正規化済み強度(0..1)に応じた色を返す
 pub fn intensity_color(val: f32) -> Color {
-    if val < 0.3 {
+    if val < 0.15 {
         Color::Blue
-    } else if val < 0.4 {
+    } else if val < 0.3 {
         Color::Cyan
-    } else if val < 0.6 {
+    } else if val < 0.5 {
         Color::Green
-    } else if val < 0.8 {
+    } else if val < 0.75 {
         Color::Yellow
     } else {
         Color::Red
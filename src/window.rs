@@ -0,0 +1,107 @@
+use crate::constants::FFT_SIZE;
+
+/// 解析窓の種類
+///
+/// FFT前にフレームへ掛ける窓関数を選択する。既定は `Hann`。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowKind {
+    /// 矩形窓（窓なし）
+    Rectangular,
+    /// Hann窓
+    Hann,
+    /// Hamming窓
+    Hamming,
+    /// Blackman窓
+    Blackman,
+}
+
+impl Default for WindowKind {
+    fn default() -> Self {
+        WindowKind::Hann
+    }
+}
+
+impl WindowKind {
+    /// CLI フラグ文字列から窓種別を解釈する（未知の値は `None`）
+    pub fn from_flag(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "rect" | "rectangular" => Some(WindowKind::Rectangular),
+            "hann" => Some(WindowKind::Hann),
+            "hamming" => Some(WindowKind::Hamming),
+            "blackman" => Some(WindowKind::Blackman),
+            _ => None,
+        }
+    }
+
+    /// `FFT_SIZE` 個の窓係数を生成する
+    pub fn coefficients(&self) -> Vec<f32> {
+        let n = FFT_SIZE;
+        let denom = (n - 1) as f32;
+        (0..n)
+            .map(|i| {
+                let x = i as f32;
+                match self {
+                    WindowKind::Rectangular => 1.0,
+                    WindowKind::Hann => {
+                        0.5 - 0.5 * (2.0 * std::f32::consts::PI * x / denom).cos()
+                    }
+                    WindowKind::Hamming => {
+                        0.54 - 0.46 * (2.0 * std::f32::consts::PI * x / denom).cos()
+                    }
+                    WindowKind::Blackman => {
+                        0.42 - 0.5 * (2.0 * std::f32::consts::PI * x / denom).cos()
+                            + 0.08 * (4.0 * std::f32::consts::PI * x / denom).cos()
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// コヒーレントゲイン `sum(w) / N`
+    ///
+    /// 窓掛けによる振幅減衰を補正するために、ビン振幅をこの値で割る。
+    pub fn coherent_gain(coeffs: &[f32]) -> f32 {
+        coeffs.iter().sum::<f32>() / coeffs.len() as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 矩形窓はすべて 1.0
+    #[test]
+    fn test_rectangular_is_unity() {
+        let c = WindowKind::Rectangular.coefficients();
+        assert_eq!(c.len(), FFT_SIZE);
+        assert!(c.iter().all(|&w| (w - 1.0).abs() < 1e-6));
+    }
+
+    /// Hann は端点が 0 付近で対称
+    #[test]
+    fn test_hann_endpoints_and_symmetry() {
+        let c = WindowKind::Hann.coefficients();
+        assert!(c[0].abs() < 1e-6);
+        assert!(c[FFT_SIZE - 1].abs() < 1e-6);
+        for i in 0..FFT_SIZE / 2 {
+            assert!((c[i] - c[FFT_SIZE - 1 - i]).abs() < 1e-5);
+        }
+    }
+
+    /// Hann のコヒーレントゲインは約 0.5
+    #[test]
+    fn test_hann_coherent_gain() {
+        let c = WindowKind::Hann.coefficients();
+        let cg = WindowKind::coherent_gain(&c);
+        assert!((cg - 0.5).abs() < 1e-2);
+    }
+
+    /// フラグ文字列の解釈
+    #[test]
+    fn test_from_flag() {
+        assert_eq!(WindowKind::from_flag("hann"), Some(WindowKind::Hann));
+        assert_eq!(WindowKind::from_flag("RECT"), Some(WindowKind::Rectangular));
+        assert_eq!(WindowKind::from_flag("blackman"), Some(WindowKind::Blackman));
+        assert_eq!(WindowKind::from_flag("nope"), None);
+    }
+}